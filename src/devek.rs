@@ -0,0 +1,242 @@
+/**
+The inverse of `vek!`/`iter!`: destructures a `Vec` or iterator against a
+slice-like pattern and evaluates to `Option<T>`.
+
+    use lit_vek::unvek;
+
+    let v = vec![1, 2, 3, 4, 5];
+    let total = unvek!(v; [first, second, rest.., last] => {
+        first + second + rest.sum::<i32>() + last
+    });
+    assert_eq!(total, Some(1 + 2 + (3 + 4) + 5));
+
+`unvek!(<expr>; [<pattern>] => <body>)` calls `IntoIterator::into_iter()`
+on `<expr>`, matches it against `<pattern>`, and evaluates to
+`Some(<body>)` if the match succeeds, `None` otherwise.
+
+The pattern is a comma-separated list of ordinary (irrefutable) patterns,
+with at most one `name..` rest binding. Patterns before the rest are
+pulled from the front with `next()`; `name..` itself binds to whatever
+`Iterator` remains after that; patterns after the rest are pulled from
+the back with `next_back()`, which requires the source to be a
+`DoubleEndedIterator`.
+
+    use lit_vek::unvek;
+
+    assert_eq!(
+        unvek!(vec![1, 2, 3]; [a, b, c] => (a, b, c)),
+        Some((1, 2, 3)));
+
+    // Too few elements: the match fails.
+    assert_eq!(unvek!(vec![1, 2]; [a, b, c] => (a, b, c)), None);
+
+    // Too many elements and no `..`: the match fails too.
+    assert_eq!(unvek!(vec![1, 2, 3, 4]; [a, b, c] => (a, b, c)), None::<(i32, i32, i32)>);
+
+    // `rest..` captures everything in between as an iterator.
+    assert_eq!(
+        unvek!(vec![1, 2, 3, 4, 5]; [first, rest.., last] => {
+            (first, rest.collect::<Vec<_>>(), last)
+        }),
+        Some((1, vec![2, 3, 4], 5)));
+
+Patterns can destructure, just like any other irrefutable Rust pattern:
+
+    use lit_vek::unvek;
+
+    let pairs = vec![(1, "a"), (2, "b")];
+    assert_eq!(
+        unvek!(pairs; [(k1, v1), (k2, v2)] => [(k1, v1), (k2, v2)]),
+        Some([(1, "a"), (2, "b")]));
+*/
+#[macro_export]
+macro_rules! unvek {
+    ($it:expr; [$($pats:tt)*] => $body:expr) => {
+        $crate::__unvek_lead!([$it] [$body] [] [] $($pats)*)
+    };
+}
+
+/** Tt-muncher: splits the pattern list (before any `name..` rest) on
+top-level commas, one slot at a time. `$it`/`$body` are threaded through
+unchanged so that the munched token list can stay at the end of each
+arm (the only way to keep an open-ended `tt` repetition unambiguous). */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unvek_lead {
+    // found the rest marker, with a slot boundary yet to be parsed
+    ([$it:expr] [$body:expr] [$([$($done:tt)*])*] [] $name:ident .. , $($tail:tt)*) => {
+        $crate::__unvek_trail!([$it] [$body] [$([$($done)*])*] $name [] [] $($tail)*)
+    };
+
+    // found the rest marker, nothing after it
+    ([$it:expr] [$body:expr] [$([$($done:tt)*])*] [] $name:ident ..) => {
+        $crate::__unvek_trail!([$it] [$body] [$([$($done)*])*] $name [] [])
+    };
+
+    // end of pattern list, no rest seen
+    ([$it:expr] [$body:expr] [$([$($done:tt)*])*] []) => {
+        $crate::__unvek_emit_fixed!([$it] [$body] [$([$($done)*])*])
+    };
+
+    // comma: ends the current (non-empty) slot
+    ([$it:expr] [$body:expr] [$([$($done:tt)*])*] [$($cur:tt)+] , $($tail:tt)*) => {
+        $crate::__unvek_lead!([$it] [$body] [$([$($done)*])* [$($cur)+]] [] $($tail)*)
+    };
+
+    // end of input: finalize the last (non-empty) slot, no rest seen
+    ([$it:expr] [$body:expr] [$([$($done:tt)*])*] [$($cur:tt)+]) => {
+        $crate::__unvek_emit_fixed!([$it] [$body] [$([$($done)*])* [$($cur)+]])
+    };
+
+    // otherwise, push one more token onto the current slot
+    ([$it:expr] [$body:expr] [$([$($done:tt)*])*] [$($cur:tt)*] $t:tt $($tail:tt)*) => {
+        $crate::__unvek_lead!([$it] [$body] [$([$($done)*])*] [$($cur)* $t] $($tail)*)
+    };
+}
+
+/** Tt-muncher: splits the pattern list after `name..` on top-level
+commas, same as `__unvek_lead` but without looking for another rest. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unvek_trail {
+    // end of pattern list
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($done:tt)*])*] []) => {
+        $crate::__unvek_reverse_trail!([$it] [$body] [$([$($lead)*])*] $restname [] [$([$($done)*])*])
+    };
+
+    // comma: ends the current (non-empty) slot
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($done:tt)*])*] [$($cur:tt)+] , $($tail:tt)*) => {
+        $crate::__unvek_trail!([$it] [$body] [$([$($lead)*])*] $restname [$([$($done)*])* [$($cur)+]] [] $($tail)*)
+    };
+
+    // end of input: finalize the last (non-empty) slot
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($done:tt)*])*] [$($cur:tt)+]) => {
+        $crate::__unvek_reverse_trail!([$it] [$body] [$([$($lead)*])*] $restname [] [$([$($done)*])* [$($cur)+]])
+    };
+
+    // otherwise, push one more token onto the current slot
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($done:tt)*])*] [$($cur:tt)*] $t:tt $($tail:tt)*) => {
+        $crate::__unvek_trail!([$it] [$body] [$([$($lead)*])*] $restname [$([$($done)*])*] [$($cur)* $t] $($tail)*)
+    };
+}
+
+/** Reverses the trailing-slot list, so the first slot processed is the
+one closest to the true end of the sequence (matching `next_back()`'s
+pop order). */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unvek_reverse_trail {
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($acc:tt)*])*] []) => {
+        $crate::__unvek_emit_rest!([$it] [$body] [$([$($lead)*])*] $restname [$([$($acc)*])*])
+    };
+
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($acc:tt)*])*] [[$($first:tt)*] $($more:tt)*]) => {
+        $crate::__unvek_reverse_trail!([$it] [$body] [$([$($lead)*])*] $restname [[$($first)*] $([$($acc)*])*] [$($more)*])
+    };
+}
+
+/** Builds the final expression when there's no `name..` rest: every
+slot is pulled from the front, and any leftover element fails the
+match. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unvek_emit_fixed {
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*]) => {
+        {
+            #[allow(clippy::redundant_closure_call)]
+            let __unvek_result = (move || -> ::std::option::Option<_> {
+                #[allow(unused_mut)]
+                let mut __unvek_it = ::std::iter::IntoIterator::into_iter($it);
+                $(
+                    let $($lead)* = ::std::iter::Iterator::next(&mut __unvek_it)?;
+                )*
+                if ::std::iter::Iterator::next(&mut __unvek_it).is_some() {
+                    return ::std::option::Option::None;
+                }
+                ::std::option::Option::Some($body)
+            })();
+            __unvek_result
+        }
+    };
+}
+
+/** Builds the final expression when there's a `name..` rest: leading
+slots pull from the front, trailing slots pull from the back (in
+reverse-written order), and `name` binds to whatever `Iterator`
+remains. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unvek_emit_rest {
+    ([$it:expr] [$body:expr] [$([$($lead:tt)*])*] $restname:ident [$([$($trail_rev:tt)*])*]) => {
+        {
+            #[allow(clippy::redundant_closure_call)]
+            let __unvek_result = (move || -> ::std::option::Option<_> {
+                #[allow(unused_mut)]
+                let mut __unvek_it = ::std::iter::IntoIterator::into_iter($it);
+                $(
+                    let $($lead)* = ::std::iter::Iterator::next(&mut __unvek_it)?;
+                )*
+                $(
+                    let $($trail_rev)* = ::std::iter::DoubleEndedIterator::next_back(&mut __unvek_it)?;
+                )*
+                let $restname = __unvek_it;
+                ::std::option::Option::Some($body)
+            })();
+            __unvek_result
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_unvek_fixed() {
+        assert_eq!(unvek!(vec![1, 2, 3]; [a, b, c] => (a, b, c)), Some((1, 2, 3)));
+        assert_eq!(unvek!(vec![1, 2]; [a, b, c] => (a, b, c)), None);
+        assert_eq!(
+            unvek!(vec![1, 2, 3, 4]; [a, b, c] => (a, b, c)),
+            None::<(i32, i32, i32)>
+        );
+        assert_eq!(unvek!(Vec::<i32>::new(); [] => ()), Some(()));
+        assert_eq!(unvek!(vec![1]; [] => ()), None);
+    }
+
+    #[test]
+    fn test_unvek_rest() {
+        assert_eq!(
+            unvek!(vec![1, 2, 3, 4, 5]; [first, rest.., last] => {
+                (first, rest.collect::<Vec<_>>(), last)
+            }),
+            Some((1, vec![2, 3, 4], 5))
+        );
+
+        assert_eq!(
+            unvek!(vec![1, 2, 3]; [a, b, rest..] => (a, b, rest.collect::<Vec<_>>())),
+            Some((1, 2, vec![3]))
+        );
+
+        assert_eq!(
+            unvek!(vec![1, 2, 3]; [rest.., y, z] => (rest.collect::<Vec<_>>(), y, z)),
+            Some((vec![1], 2, 3))
+        );
+
+        assert_eq!(
+            unvek!(vec![1]; [a, b, rest.., c] => (a, b, rest.collect::<Vec<_>>(), c)),
+            None
+        );
+
+        assert_eq!(
+            unvek!(vec![1, 2]; [rest..] => rest.collect::<Vec<_>>()),
+            Some(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_unvek_destructure() {
+        let pairs = vec![(1, "a"), (2, "b")];
+        assert_eq!(
+            unvek!(pairs; [(k1, v1), (k2, v2)] => [(k1, v1), (k2, v2)]),
+            Some([(1, "a"), (2, "b")])
+        );
+    }
+}
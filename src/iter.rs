@@ -1,10 +1,15 @@
 /** Iterator type returned by `cycle_n()` */
 #[derive(Clone, Debug)]
 pub struct CycleN<I> {
-    // Both `orig` and `iter` are in Option so that we don't have to
-    // call into_iter() or clone() more than necessary.
+    // `front`/`back` are the passes currently feeding `next()`/`next_back()`.
+    // While 2 or more passes remain they're distinct (freshly cloned from
+    // `orig`); once only one pass remains, it's kept solely in `front` and
+    // shared by both ends (`back` and `orig` become `None`). This split is
+    // what lets `next()` and `next_back()` be interleaved across a pass
+    // boundary without reordering or duplicating elements.
     orig: Option<I>,
-    iter: Option<I>,
+    front: Option<I>,
+    back: Option<I>,
     n: usize,
 }
 
@@ -25,23 +30,27 @@ where
     match n {
         0 => CycleN {
             orig: None,
-            iter: None,
+            front: None,
+            back: None,
             n,
         },
         1 => {
             let it = it.into_iter();
             CycleN {
                 orig: None,
-                iter: Some(it),
+                front: Some(it),
+                back: None,
                 n,
             }
         }
         _ => {
-            let i1 = it.into_iter();
-            let i2 = i1.clone();
+            let orig = it.into_iter();
+            let front = orig.clone();
+            let back = orig.clone();
             CycleN {
-                orig: Some(i1),
-                iter: Some(i2),
+                orig: Some(orig),
+                front: Some(front),
+                back: Some(back),
                 n,
             }
         }
@@ -52,21 +61,124 @@ impl<I: Iterator + Clone> Iterator for CycleN<I> {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(it) = &mut self.iter {
+        while let Some(it) = &mut self.front {
             if let Some(x) = it.next() {
                 return Some(x);
             }
             self.n -= 1;
-            if self.n < 2 {
-                self.iter = self.orig.take();
-            } else {
-                self.iter = self.orig.clone();
+            self.front = match self.n {
+                0 => None,
+                1 => {
+                    self.orig = None;
+                    self.back.take()
+                }
+                _ => self.orig.clone(),
+            };
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (front_lo, front_hi) = match &self.front {
+            Some(it) => it.size_hint(),
+            None => (0, Some(0)),
+        };
+        if self.n < 2 {
+            // n == 0: front is None too, so this is already (0, Some(0)).
+            // n == 1: front alone holds the sole remaining pass, already
+            // reflecting whatever either end has consumed from it so far.
+            return (front_lo, front_hi);
+        }
+        let (back_lo, back_hi) = self.back.as_ref().unwrap().size_hint();
+        let (pass_lo, pass_hi) = self.orig.as_ref().unwrap().size_hint();
+        let pool = self.n - 2;
+        let lo = front_lo
+            .saturating_add(back_lo)
+            .saturating_add(pass_lo.saturating_mul(pool));
+        let hi = front_hi.zip(back_hi).and_then(|(f, b)| f.checked_add(b)).and_then(|fb| {
+            pass_hi
+                .and_then(|p| p.checked_mul(pool))
+                .and_then(|p| fb.checked_add(p))
+        });
+        (lo, hi)
+    }
+}
+
+impl<I: Iterator + Clone + ExactSizeIterator> ExactSizeIterator for CycleN<I> {
+    fn len(&self) -> usize {
+        self.size_hint().0
+    }
+}
+
+impl<I: Iterator + Clone + DoubleEndedIterator> DoubleEndedIterator for CycleN<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(it) = &mut self.back {
+            if let Some(x) = it.next_back() {
+                return Some(x);
             }
+            self.n -= 1;
+            self.back = match self.n {
+                1 => {
+                    self.orig = None;
+                    None
+                }
+                _ => self.orig.clone(),
+            };
+        }
+        // No independent `back` pass remains; whatever's left (if
+        // anything) is the sole pass in `front`, shared with `next()`.
+        while let Some(it) = &mut self.front {
+            if let Some(x) = it.next_back() {
+                return Some(x);
+            }
+            self.n -= 1;
+            self.front = None;
         }
         None
     }
 }
 
+/** Iterator type returned by `spread_if()`, used to expand the
+`if cond => ...xs` form in `vek!`/`iter!` without depending on a crate
+like `itertools` for `Either`. */
+#[derive(Clone, Debug)]
+pub enum EitherIter<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<T, L: Iterator<Item = T>, R: Iterator<Item = T>> Iterator for EitherIter<L, R> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            EitherIter::Left(it) => it.next(),
+            EitherIter::Right(it) => it.next(),
+        }
+    }
+}
+
+/** Iterator adaptor that spreads `xs` if `cond` is true, or nothing
+otherwise.
+
+    use lit_vek::spread_if;
+
+    assert!(spread_if(true, [1, 2, 3]).eq([1, 2, 3]));
+    assert!(spread_if(false, [1, 2, 3]).eq([]));
+
+This is mostly to enable the `vek![if cond => ...xs]` syntax.
+*/
+pub fn spread_if<I: IntoIterator>(
+    cond: bool,
+    xs: I,
+) -> EitherIter<I::IntoIter, ::std::iter::Empty<I::Item>> {
+    if cond {
+        EitherIter::Left(xs.into_iter())
+    } else {
+        EitherIter::Right(::std::iter::empty())
+    }
+}
+
 /**
 Chain one more elements or iterables together into one sequence, using "spread"
 syntax.
@@ -120,12 +232,87 @@ arguments rather than a mix of iterables and single elements.
 ```ignore
     chain![a, b, iter::once(c)] == iter![...a, ...b, c]
 ```
+
+# Comprehensions
+
+`iter!`/`vek!` also accept Python-style comprehension syntax: an output
+expression followed by one or more `for <pat> in <iter>` clauses, each
+with an optional `if <cond>` guard.
+
+    use lit_vek::iter;
+
+    assert_eq!(
+        iter![x * 2 for x in 0..10 if x % 2 == 0].collect::<Vec<_>>(),
+        vec![0, 4, 8, 12, 16]);
+
+`<pat>` can be any irrefutable pattern, so destructuring works:
+
+    use {lit_vek::iter, std::collections::HashMap};
+
+    let map = HashMap::from([("a", 1), ("b", 2)]);
+    let mut vs: Vec<_> = iter![*v for (_, v) in &map].collect();
+    vs.sort();
+    assert_eq!(vs, [1, 2]);
+
+Multiple `for` clauses compose like nested loops (flat-mapping the outer
+clauses over the inner ones):
+
+    use lit_vek::iter;
+
+    assert_eq!(
+        iter![(i, j) for i in 0..3 for j in 0..i].collect::<Vec<_>>(),
+        [(1, 0), (2, 0), (2, 1)]);
+
+A comprehension is parsed as the whole content of the `iter!`/`vek!`
+invocation. To combine one with ordinary spread syntax, nest it inside
+a `...`:
+
+    use lit_vek::{iter, vek};
+
+    assert_eq!(
+        vek![-1, ...iter![x * x for x in 1..4], 100],
+        [-1, 1, 4, 9, 100]);
+
+# Conditional and optional elements
+
+An `if <cond> => <elem>` element contributes zero or one items, without
+breaking the single-iterator expansion of the rest of the list:
+
+    use lit_vek::vek;
+
+    let is_alpha = true;
+    assert_eq!(
+        vek![1, 2, if is_alpha => 3, 4],
+        [1, 2, 3, 4]);
+
+    let is_alpha = false;
+    assert_eq!(
+        vek![1, 2, if is_alpha => 3, 4],
+        [1, 2, 4]);
+
+`if <cond> => ...<xs>` is the same idea applied to a whole spread: it
+includes all of `xs`, or none of it.
+
+    use lit_vek::vek;
+
+    let xs = [3, 4];
+    let include_xs = false;
+    assert_eq!(
+        vek![1, 2, if include_xs => ...xs, 9],
+        [1, 2, 9]);
 */
 #[macro_export]
 macro_rules! iter {
     // empty
     () => { ::std::iter::empty() };
 
+    // `if <cond> => ...`: look ahead for the disambiguating `=>` before
+    // committing, so a plain `if cond { .. } else { .. }` value (which
+    // has no `=>`) falls through to the ordinary element arms below.
+    (if $($tail:tt)*) => {
+        $crate::__iter_if_lookahead!([] $($tail)*)
+    };
+
     // [x; n]
     ($x:tt; $n:expr) => {
         ::std::iter::repeat($x).take($n)
@@ -189,11 +376,167 @@ macro_rules! iter {
     ($x:expr $(, $($tail:tt)*)?) => {
         $crate::iter![...[$x], $($($tail)*)? ]
     };
+
+    // comprehension: `<out> for <pat> in <iter> ...`, tried last since
+    // every other form above matches plain lists and spreads first.
+    ($($all:tt)+) => {
+        $crate::__iter_compr_out!([] $($all)+)
+    };
+}
+
+/** Tt-muncher: scans the tokens after `if` for a top-level `=>` before
+the next top-level comma or `for`. If found, this is the `if <cond> =>
+...` sugar (the accumulated condition is parenthesized before use, so it
+can't be misparsed as a struct literal). A top-level `for` instead means
+the whole `if ...` is actually the output expression of a comprehension
+(see `__iter_compr_out`), so it's handed off to clause parsing directly.
+Otherwise (a top-level comma, or running out of tokens) the `if ...` was
+an ordinary `if`/`else` value, so it's shifted whole into a starting
+literal array just like any other element. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __iter_if_lookahead {
+    // found `=>`: `if <cond> => ...xs`
+    ([$($cond:tt)*] => ...$xs:expr $(, $($tail:tt)*)?) => {
+        $crate::iter![...$crate::spread_if(($($cond)*), $xs) $(, $($tail)*)?]
+    };
+
+    // found `=>`: `if <cond> => elem`
+    ([$($cond:tt)*] => $elem:expr $(, $($tail:tt)*)?) => {
+        $crate::iter![...(($($cond)*).then(|| $elem)) $(, $($tail)*)?]
+    };
+
+    // hit a top-level `for` before `=>`: this is a comprehension whose
+    // output expression is the `if ...` seen so far
+    ([$($cond:tt)*] for $($rest:tt)*) => {
+        $crate::__iter_compr_clause!([if $($cond)*] for $($rest)*)
+    };
+
+    // hit a top-level comma before `=>`: not the special form, the
+    // whole `if ...` so far is one ordinary element
+    ([$($cond:tt)*] , $($tail:tt)*) => {
+        $crate::iter![...[if $($cond)*], $($tail)*]
+    };
+
+    // ran out of tokens before `=>`: same, and it's the last element
+    ([$($cond:tt)*]) => {
+        $crate::iter![...[if $($cond)*]]
+    };
+
+    // otherwise, shift one more token onto the accumulated condition
+    ([$($cond:tt)*] $t:tt $($tail:tt)*) => {
+        $crate::__iter_if_lookahead!([$($cond)* $t] $($tail)*)
+    };
+}
+
+/** Tt-muncher: accumulates the comprehension's output expression until it
+hits the first `for`, then hands off to `__iter_compr_clause`. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __iter_compr_out {
+    ([$($out:tt)*] for $($rest:tt)*) => {
+        $crate::__iter_compr_clause!([$($out)*] for $($rest)*)
+    };
+    ([$($out:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::__iter_compr_out!([$($out)* $t] $($rest)*)
+    };
+}
+
+/** Parses one `for <pat> in` clause head, then hands the rest of the
+tokens to `__iter_compr_iter` to munch the iterable expression. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __iter_compr_clause {
+    ([$($out:tt)*] for $pat:pat in $($rest:tt)*) => {
+        $crate::__iter_compr_iter!([$($out)*] [$pat] [] $($rest)*)
+    };
+}
+
+/** Tt-muncher: accumulates a clause's `<iter>` expression until it hits
+`if`, a nested `for`, or the end of input. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __iter_compr_iter {
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] if $($rest:tt)*) => {
+        $crate::__iter_compr_cond!([$($out)*] [$pat] [$($ie)*] [] $($rest)*)
+    };
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] for $($rest:tt)*) => {
+        $crate::__iter_compr_emit!([$($out)*] [$pat] [$($ie)*] [] for $($rest)*)
+    };
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*]) => {
+        $crate::__iter_compr_emit!([$($out)*] [$pat] [$($ie)*] [])
+    };
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::__iter_compr_iter!([$($out)*] [$pat] [$($ie)* $t] $($rest)*)
+    };
+}
+
+/** Tt-muncher: accumulates a clause's `if <cond>` guard until it hits a
+nested `for` or the end of input. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __iter_compr_cond {
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] [$($c:tt)*] for $($rest:tt)*) => {
+        $crate::__iter_compr_emit!([$($out)*] [$pat] [$($ie)*] [$($c)*] for $($rest)*)
+    };
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] [$($c:tt)*]) => {
+        $crate::__iter_compr_emit!([$($out)*] [$pat] [$($ie)*] [$($c)*])
+    };
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] [$($c:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::__iter_compr_cond!([$($out)*] [$pat] [$($ie)*] [$($c)* $t] $($rest)*)
+    };
+}
+
+/** Emits one clause: the innermost clause (no further `for`) lowers to
+`map()`, and every outer clause wraps the recursively-built inner
+expression in `flat_map()`; a clause with an `if` guard puts a
+`filter()` in front of its `map()`/`flat_map()`. */
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __iter_compr_emit {
+    // innermost clause, no guard
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] []) => {
+        ::std::iter::Iterator::map(
+            ::std::iter::IntoIterator::into_iter(($($ie)*)),
+            move |$pat| ($($out)*),
+        )
+    };
+
+    // innermost clause, with guard
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] [$($c:tt)+]) => {
+        ::std::iter::Iterator::map(
+            ::std::iter::Iterator::filter(
+                ::std::iter::IntoIterator::into_iter(($($ie)*)),
+                move |$pat| ($($c)+),
+            ),
+            move |$pat| ($($out)*),
+        )
+    };
+
+    // outer clause, no guard: flat-map over the remaining clauses
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] [] for $($rest:tt)*) => {
+        ::std::iter::Iterator::flat_map(
+            ::std::iter::IntoIterator::into_iter(($($ie)*)),
+            move |$pat| $crate::__iter_compr_clause!([$($out)*] for $($rest)*),
+        )
+    };
+
+    // outer clause, with guard: filter then flat-map
+    ([$($out:tt)*] [$pat:pat] [$($ie:tt)*] [$($c:tt)+] for $($rest:tt)*) => {
+        ::std::iter::Iterator::flat_map(
+            ::std::iter::Iterator::filter(
+                ::std::iter::IntoIterator::into_iter(($($ie)*)),
+                move |$pat| ($($c)+),
+            ),
+            move |$pat| $crate::__iter_compr_clause!([$($out)*] for $($rest)*),
+        )
+    };
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vek;
 
     #[test]
     fn test_cycle_n() {
@@ -204,6 +547,55 @@ mod tests {
         assert!(cycle_n(0..0, 10).eq([]));
     }
 
+    #[test]
+    fn test_cycle_n_size_hint() {
+        assert_eq!(cycle_n(1..4, 0).size_hint(), (0, Some(0)));
+        assert_eq!(cycle_n(1..4, 1).size_hint(), (3, Some(3)));
+        assert_eq!(cycle_n(1..4, 3).size_hint(), (9, Some(9)));
+
+        let mut it = cycle_n(1..4, 2);
+        it.next();
+        assert_eq!(it.size_hint(), (5, Some(5)));
+    }
+
+    #[test]
+    fn test_cycle_n_exact_size() {
+        assert_eq!(cycle_n(1..4, 3).len(), 9);
+        assert_eq!(cycle_n(1..4, 0).len(), 0);
+    }
+
+    #[test]
+    fn test_cycle_n_double_ended() {
+        assert!(cycle_n(1..4, 2).rev().eq([3, 2, 1, 3, 2, 1]));
+
+        let mut it = cycle_n(1..4, 2);
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.next_back(), Some(3));
+        assert_eq!(it.collect::<Vec<_>>(), vec![2, 3, 1, 2]);
+    }
+
+    // Regression test: `next()`/`next_back()` interleaved across the
+    // boundary between passes used to duplicate and reorder elements
+    // (the "current" pass was conflated with the "next" one).
+    #[test]
+    fn test_cycle_n_double_ended_interleaved() {
+        let mut it = cycle_n(1..4, 2);
+        let mut got = Vec::new();
+        for _ in 0..3 {
+            got.push(it.next().unwrap());
+            got.push(it.next_back().unwrap());
+        }
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+        assert_eq!(got, vec![1, 3, 2, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_spread_if() {
+        assert!(spread_if(true, [1, 2, 3]).eq([1, 2, 3]));
+        assert!(spread_if(false, [1, 2, 3]).eq([]));
+    }
+
     #[test]
     fn test_iter() {
         assert_eq!(Vec::<u32>::new(), iter![].collect::<Vec<_>>());
@@ -222,4 +614,71 @@ mod tests {
             iter![1, ...[2,3], 4, ...[], 5, ...[6]].collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn test_comprehension() {
+        assert_eq!(
+            vec![0, 2, 4, 6, 8],
+            iter![x for x in 0..10 if x % 2 == 0].collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![0, 4, 8, 12, 16],
+            iter![x * 2 for x in 0..10 if x % 2 == 0].collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![(1, 0), (2, 0), (2, 1)],
+            iter![(i, j) for i in 0..3 for j in 0..i].collect::<Vec<_>>()
+        );
+
+        let pairs = [("a", 1), ("b", 2)];
+        assert_eq!(
+            vec![1, 2],
+            iter![v for (_, v) in pairs].collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![-1, 1, 4, 9, 100],
+            vek![-1, ...iter![x * x for x in 1..4], 100]
+        );
+
+        // An `if`/`else` output expression must not be swallowed by the
+        // `if <cond> => ...` conditional-element sugar.
+        assert_eq!(
+            vec![2, 1, 0, 1, 2],
+            iter![if x > 0 { x } else { -x } for x in -2..3].collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_conditional() {
+        let is_alpha = true;
+        assert_eq!(vec![1, 2, 3, 4], vek![1, 2, if is_alpha => 3, 4]);
+
+        let is_alpha = false;
+        assert_eq!(vec![1, 2, 4], vek![1, 2, if is_alpha => 3, 4]);
+
+        let xs = [3, 4];
+        let include_xs = true;
+        assert_eq!(vec![1, 2, 3, 4, 9], vek![1, 2, if include_xs => ...xs, 9]);
+
+        let include_xs = false;
+        assert_eq!(vec![1, 2, 9], vek![1, 2, if include_xs => ...xs, 9]);
+    }
+
+    #[test]
+    fn test_if_else_value_not_conditional_sugar() {
+        // A plain `if`/`else` expression used as an ordinary element
+        // (no `=>`) must keep working, not be swallowed by the
+        // `if <cond> => ...` sugar above.
+        let x = true;
+        assert_eq!(vec![1, 2, 4], vek![1, if x { 2 } else { 3 - 1 }, 4]);
+
+        let x = false;
+        assert_eq!(vec![1, 3, 4], vek![1, if x { 2 } else { 3 }, 4]);
+
+        // as the last element, with no trailing comma
+        assert_eq!(vec![1, 3], vek![1, if x { 2 } else { 3 }]);
+    }
 }
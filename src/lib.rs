@@ -21,10 +21,20 @@ literal sequences.
     # use std::collections::VecDeque;
     let d: VecDeque<_> = iter![1, 2, 3, ...arr, 7, 8, 9].collect();
 ```
+
+`vek!`/`iter!` also understand Python-style comprehensions
+(`iter![x * 2 for x in xs if x % 2 == 0]`) and conditional/optional elements
+(`vek![if cond => x, ...]`, `vek![if cond => ...xs, ...]`) -- see `iter!`
+for the full syntax.
+
+`unvek!` is the inverse of `vek!`/`iter!`: it destructures a `Vec` or
+iterator against a slice-like pattern, with an optional `name..` to
+capture everything in between as an iterator.
 */
+mod devek;
 mod iter;
 
-pub use iter::{cycle_n, CycleN};
+pub use iter::{cycle_n, spread_if, CycleN, EitherIter};
 
 /**
 A drop-in replacement for `vec![]` that adds "spread" syntax.
@@ -114,7 +124,13 @@ macro_rules! vek {
     () => { Vec::new() };
 
     ($($tail:tt)*) => {
-        ::std::iter::Iterator::collect::<Vec<_>>($crate::iter![$($tail)*])
+        {
+            let __vek_iter = $crate::iter![$($tail)*];
+            let (__vek_lo, _) = ::std::iter::Iterator::size_hint(&__vek_iter);
+            let mut __vek_vec = ::std::vec::Vec::with_capacity(__vek_lo);
+            ::std::iter::Extend::extend(&mut __vek_vec, __vek_iter);
+            __vek_vec
+        }
     };
 }
 